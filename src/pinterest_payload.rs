@@ -1,10 +1,15 @@
 use anyhow::anyhow;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::exports::edgee::components::data_collection::{Consent, Data, Dict, Event};
 
+/// Pinterest's OAuth token endpoint, used to exchange a refresh token for a
+/// fresh access token. See https://developers.pinterest.com/docs/getting-started/authentication/
+const PINTEREST_TOKEN_URL: &str = "https://api.pinterest.com/v5/oauth/token";
+
 #[derive(Serialize, Debug, Default)]
 pub(crate) struct PinterestPayload {
     pub data: Vec<PinterestEvent>,
@@ -13,9 +18,24 @@ pub(crate) struct PinterestPayload {
     #[serde(skip)]
     pub access_token: String,
     #[serde(skip)]
+    pub refresh_token: Option<String>,
+    #[serde(skip)]
+    pub client_id: Option<String>,
+    #[serde(skip)]
+    pub client_secret: Option<String>,
+    #[serde(skip)]
+    pub access_token_expires_at: Option<u64>,
+    #[serde(skip)]
     pub is_test: bool,
 }
 
+/// Pinterest's OAuth token endpoint response, for a `grant_type=refresh_token` exchange.
+#[derive(Deserialize, Debug)]
+struct RefreshTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
 impl PinterestPayload {
     pub fn new(settings: Dict) -> anyhow::Result<Self> {
         let cred: HashMap<String, String> = settings
@@ -40,13 +60,146 @@ impl PinterestPayload {
             .map(|s| s.to_lowercase() == "true")
             .unwrap_or(false);
 
+        let refresh_token = cred.get("pinterest_refresh_token").cloned();
+        let client_id = cred.get("pinterest_client_id").cloned();
+        let client_secret = cred.get("pinterest_client_secret").cloned();
+
         Ok(Self {
             data: vec![],
             ad_account_id,
             access_token,
+            refresh_token,
+            client_id,
+            client_secret,
+            access_token_expires_at: None,
             is_test,
         })
     }
+
+    /// Whether the access token is stale and should be refreshed before being used.
+    pub fn access_token_is_stale(&self) -> bool {
+        match self.access_token_expires_at {
+            Some(expires_at) => now_unix_secs() >= expires_at,
+            None => false,
+        }
+    }
+
+    /// The access token to send as `Authorization: Bearer`, refreshing it first if it's
+    /// stale. Callers that build the outbound Pinterest CAPI request must read the token
+    /// through this method rather than the `access_token` field directly, so the request
+    /// never goes out with an expired token.
+    pub async fn valid_access_token(&mut self) -> anyhow::Result<&str> {
+        if self.access_token_is_stale() {
+            self.refresh_access_token().await?;
+        }
+        Ok(&self.access_token)
+    }
+
+    /// Exchange the refresh token at Pinterest's OAuth token endpoint for a fresh
+    /// access token, using `grant_type=refresh_token` with HTTP Basic client auth.
+    /// On success, the payload's `access_token` is updated so the outbound request
+    /// carries a valid `Authorization: Bearer` header.
+    pub async fn refresh_access_token(&mut self) -> anyhow::Result<()> {
+        let refresh_token = self
+            .refresh_token
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing Pinterest Refresh Token"))?;
+        let client_id = self
+            .client_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing Pinterest Client ID"))?;
+        let client_secret = self
+            .client_secret
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing Pinterest Client Secret"))?;
+
+        let response: RefreshTokenResponse = reqwest::Client::new()
+            .post(PINTEREST_TOKEN_URL)
+            .basic_auth(client_id, Some(client_secret))
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        self.access_token = response.access_token;
+        self.access_token_expires_at = Some(now_unix_secs() + response.expires_in);
+
+        Ok(())
+    }
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Pinterest standard event names
+///
+/// Pinterest defines a fixed set of standard conversion events (see
+/// https://developers.pinterest.com/docs/api/v5/events-create). Any event name that
+/// doesn't match one of these is treated as a personalized (custom) event and sent
+/// through as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinterestEventName {
+    Checkout,
+    AddToCart,
+    PageVisit,
+    ViewCategory,
+    Search,
+    Signup,
+    Lead,
+    WatchVideo,
+    AddToWishlist,
+    Custom(String),
+}
+
+impl PinterestEventName {
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            PinterestEventName::Checkout => "checkout",
+            PinterestEventName::AddToCart => "add_to_cart",
+            PinterestEventName::PageVisit => "page_visit",
+            PinterestEventName::ViewCategory => "view_category",
+            PinterestEventName::Search => "search",
+            PinterestEventName::Signup => "signup",
+            PinterestEventName::Lead => "lead",
+            PinterestEventName::WatchVideo => "watch_video",
+            PinterestEventName::AddToWishlist => "add_to_wishlist",
+            PinterestEventName::Custom(name) => name.as_str(),
+        }
+    }
+
+    /// Whether Pinterest requires `custom_data.value`/`currency` for this event type.
+    pub fn requires_value_and_currency(&self) -> bool {
+        matches!(
+            self,
+            PinterestEventName::Checkout | PinterestEventName::AddToCart
+        )
+    }
+}
+
+impl From<&str> for PinterestEventName {
+    fn from(event_name: &str) -> Self {
+        match event_name {
+            "checkout" => PinterestEventName::Checkout,
+            "add_to_cart" => PinterestEventName::AddToCart,
+            "page_visit" => PinterestEventName::PageVisit,
+            "view_category" => PinterestEventName::ViewCategory,
+            "search" => PinterestEventName::Search,
+            "signup" => PinterestEventName::Signup,
+            "lead" => PinterestEventName::Lead,
+            "watch_video" => PinterestEventName::WatchVideo,
+            "add_to_wishlist" => PinterestEventName::AddToWishlist,
+            other => PinterestEventName::Custom(other.to_string()),
+        }
+    }
 }
 
 /// Pinterest Event
@@ -77,26 +230,26 @@ pub struct PinterestEvent {
 // To know more about the user data structure, check the online documentation: https://developers.pinterest.com/docs/api/v5/events-create
 #[derive(Serialize, Debug, Default)]
 pub struct UserData {
-    #[serde(rename = "em", skip_serializing_if = "Option::is_none")]
-    pub email: Option<String>, // hashed email SHA256
-    #[serde(rename = "ph", skip_serializing_if = "Option::is_none")]
-    pub phone_number: Option<String>, // hashed phone number SHA256
-    #[serde(rename = "fn", skip_serializing_if = "Option::is_none")]
-    pub first_name: Option<String>, // hashed
-    #[serde(rename = "ln", skip_serializing_if = "Option::is_none")]
-    pub last_name: Option<String>, // hashed
-    #[serde(rename = "db", skip_serializing_if = "Option::is_none")]
-    pub date_of_birth: Option<String>, // hashed
-    #[serde(rename = "ge", skip_serializing_if = "Option::is_none")]
-    pub gender: Option<String>, // hashed
-    #[serde(rename = "ct", skip_serializing_if = "Option::is_none")]
-    pub city: Option<String>, // hashed
-    #[serde(rename = "st", skip_serializing_if = "Option::is_none")]
-    pub state: Option<String>, // hashed
-    #[serde(rename = "zp", skip_serializing_if = "Option::is_none")]
-    pub zip_code: Option<String>, // hashed
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub country: Option<String>, // hashed
+    #[serde(rename = "em", skip_serializing_if = "Vec::is_empty")]
+    pub email: Vec<String>, // hashed email(s) SHA256
+    #[serde(rename = "ph", skip_serializing_if = "Vec::is_empty")]
+    pub phone_number: Vec<String>, // hashed phone number(s) SHA256
+    #[serde(rename = "fn", skip_serializing_if = "Vec::is_empty")]
+    pub first_name: Vec<String>, // hashed
+    #[serde(rename = "ln", skip_serializing_if = "Vec::is_empty")]
+    pub last_name: Vec<String>, // hashed
+    #[serde(rename = "db", skip_serializing_if = "Vec::is_empty")]
+    pub date_of_birth: Vec<String>, // hashed
+    #[serde(rename = "ge", skip_serializing_if = "Vec::is_empty")]
+    pub gender: Vec<String>, // hashed
+    #[serde(rename = "ct", skip_serializing_if = "Vec::is_empty")]
+    pub city: Vec<String>, // hashed
+    #[serde(rename = "st", skip_serializing_if = "Vec::is_empty")]
+    pub state: Vec<String>, // hashed
+    #[serde(rename = "zp", skip_serializing_if = "Vec::is_empty")]
+    pub zip_code: Vec<String>, // hashed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub country: Vec<String>, // hashed
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_id: Option<String>,
@@ -110,9 +263,11 @@ pub struct UserData {
 
 impl PinterestEvent {
     pub fn new(edgee_event: &Event, event_name: &str) -> anyhow::Result<Self> {
+        let event_name = PinterestEventName::from(event_name);
+
         // Default pinterest event
         let mut pinterest_event = PinterestEvent {
-            event_name: event_name.to_string(),
+            event_name: event_name.as_wire_str().to_string(),
             event_time: edgee_event.timestamp,
             event_id: edgee_event.uuid.clone(),
             event_source_url: None,
@@ -160,30 +315,90 @@ impl PinterestEvent {
         }
 
         // Set user properties
+        // Each match key accepts either a single value or a JSON array of values,
+        // so a user known by several emails/phone numbers can be matched on any of them.
         for (key, value) in user_properties.iter() {
             match key.as_str() {
-                "email" => user_data.email = Some(hash_value(value)),
-                "phone_number" => user_data.phone_number = Some(hash_value(value)),
-                "first_name" => user_data.first_name = Some(hash_value(value)),
-                "last_name" => user_data.last_name = Some(hash_value(value)),
-                "gender" => user_data.gender = Some(hash_value(value)),
-                "date_of_birth" => user_data.date_of_birth = Some(hash_value(value)),
-                "city" => user_data.city = Some(hash_value(value)),
-                "state" => user_data.state = Some(hash_value(value)),
-                "zip_code" => user_data.zip_code = Some(hash_value(value)),
-                "country" => user_data.country = Some(hash_value(value)),
+                "email" => {
+                    user_data.email = hash_match_values(value, |v| hash_pii(PiiField::Email, v))
+                }
+                "phone_number" => {
+                    user_data.phone_number =
+                        hash_match_values(value, |v| hash_pii(PiiField::Phone, v))
+                }
+                "first_name" => {
+                    user_data.first_name =
+                        hash_match_values(value, |v| hash_pii(PiiField::FirstName, v))
+                }
+                "last_name" => {
+                    user_data.last_name =
+                        hash_match_values(value, |v| hash_pii(PiiField::LastName, v))
+                }
+                "gender" => {
+                    user_data.gender = hash_match_values(value, |v| hash_pii(PiiField::Gender, v))
+                }
+                "date_of_birth" => user_data.date_of_birth = hash_match_values(value, hash_value),
+                "city" => {
+                    user_data.city = hash_match_values(value, |v| hash_pii(PiiField::City, v))
+                }
+                "state" => {
+                    user_data.state = hash_match_values(value, |v| hash_pii(PiiField::State, v))
+                }
+                "zip_code" => {
+                    user_data.zip_code = hash_match_values(value, |v| hash_pii(PiiField::Zip, v))
+                }
+                "country" => {
+                    user_data.country = hash_match_values(value, |v| hash_pii(PiiField::Country, v))
+                }
                 _ => {
                     // do nothing
                 }
             }
         }
 
-        if user_data.email.is_none() {
+        if user_data.email.is_empty() {
             return Err(anyhow!("User properties must contain email"));
         }
 
         pinterest_event.user_data = user_data;
 
+        // Set custom data from the event's track/ecommerce properties
+        if let Data::Track(ref data) = edgee_event.data {
+            if let Some(custom_data) = pinterest_event.custom_data.as_mut() {
+                for (key, value) in data.properties.iter() {
+                    match key.as_str() {
+                        // Identifiers/free text: never coerce to bool/number, which would
+                        // mangle leading zeros or lose precision on long numeric-looking ids.
+                        "currency" | "order_id" | "search_string" | "opt_out_type" => {
+                            custom_data
+                                .insert(key.to_string(), serde_json::Value::String(value.clone()));
+                        }
+                        // Genuinely numeric/boolean fields, plus structured line-item data.
+                        "value" | "num_items" | "content_ids" | "contents" => {
+                            custom_data.insert(key.to_string(), parse_value(value));
+                        }
+                        _ => {
+                            // do nothing
+                        }
+                    }
+                }
+            }
+        }
+
+        // Some standard events require value/currency in custom_data to be useful to Pinterest
+        if event_name.requires_value_and_currency() {
+            let has_value_and_currency = pinterest_event
+                .custom_data
+                .as_ref()
+                .is_some_and(|data| data.contains_key("value") && data.contains_key("currency"));
+            if !has_value_and_currency {
+                return Err(anyhow!(
+                    "Event '{}' requires custom_data.value and custom_data.currency",
+                    event_name.as_wire_str()
+                ));
+            }
+        }
+
         Ok(pinterest_event)
     }
 }
@@ -191,17 +406,25 @@ impl PinterestEvent {
 /// Parse value
 ///
 /// This function is used to parse the value of a property.
-/// It converts the value to a JSON value.
-/// TODO: add object and array support
+/// It converts the value to a JSON value, trying to parse it as a JSON
+/// object or array first (e.g. `content_ids` or `contents` line items) and
+/// falling back to scalar coercion otherwise.
 pub(crate) fn parse_value(value: &str) -> serde_json::Value {
+    if let Ok(parsed @ (serde_json::Value::Object(_) | serde_json::Value::Array(_))) =
+        serde_json::from_str(value)
+    {
+        return parsed;
+    }
+
     if value == "true" {
         serde_json::Value::from(true)
     } else if value == "false" {
         serde_json::Value::from(false)
-    } else if value.parse::<f64>().is_ok() {
-        serde_json::Value::Number(value.parse().unwrap())
     } else {
-        serde_json::Value::String(value.to_string())
+        value
+            .parse::<serde_json::Number>()
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
     }
 }
 
@@ -213,3 +436,335 @@ pub(crate) fn hash_value(input: &str) -> String {
     hasher.update(input.as_bytes());
     format!("{:x}", hasher.finalize())
 }
+
+/// The kind of PII a user property holds, so it can be normalized to
+/// Pinterest's spec before hashing. See https://developers.pinterest.com/docs/api/v5/events-create
+/// for the expected normalization per field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PiiField {
+    Email,
+    Phone,
+    FirstName,
+    LastName,
+    City,
+    State,
+    Zip,
+    Country,
+    Gender,
+}
+
+/// Normalize a PII value per Pinterest's matching rules, ahead of hashing.
+pub(crate) fn normalize_pii(field: PiiField, value: &str) -> String {
+    match field {
+        PiiField::Email => value.trim().to_lowercase(),
+        PiiField::Phone => {
+            let trimmed = value.trim();
+            if let Some(after_plus) = trimmed.strip_prefix('+') {
+                // The country code is the leading run of digits. If it's followed by a
+                // separator (space, dash, ...) rather than running straight into the
+                // national number, strip a trunk "0" from the national part only, e.g.
+                // "+33 0612345678" -> "33612345678", without touching area-code digits
+                // that happen to be zero when there's no such separator.
+                let mut country_code = String::new();
+                let mut rest = after_plus;
+                for (i, c) in after_plus.char_indices() {
+                    if c.is_ascii_digit() {
+                        country_code.push(c);
+                        rest = &after_plus[i + c.len_utf8()..];
+                    } else {
+                        rest = &after_plus[i..];
+                        break;
+                    }
+                }
+                let national_digits: String = rest.chars().filter(char::is_ascii_digit).collect();
+                let national = national_digits
+                    .strip_prefix('0')
+                    .unwrap_or(&national_digits);
+                format!("{country_code}{national}")
+            } else {
+                let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+                digits.strip_prefix('0').unwrap_or(&digits).to_string()
+            }
+        }
+        PiiField::FirstName | PiiField::LastName | PiiField::City | PiiField::State => value
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_ascii_punctuation())
+            .collect(),
+        PiiField::Zip => {
+            let zip: String = value
+                .trim()
+                .to_lowercase()
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '-')
+                .collect();
+            if zip.chars().all(|c| c.is_ascii_digit()) && zip.len() > 5 {
+                zip[..5].to_string()
+            } else {
+                zip
+            }
+        }
+        PiiField::Country | PiiField::Gender => value.trim().to_lowercase(),
+    }
+}
+
+/// Whether a value is already a 64-char lowercase hex string, i.e. has already
+/// been SHA-256 hashed upstream and must not be hashed again.
+pub(crate) fn is_pre_hashed(value: &str) -> bool {
+    value.len() == 64
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+/// Normalize a PII value for its field kind, then SHA-256 hash it, unless it's
+/// already a pre-hashed value, in which case it's passed through unchanged.
+pub(crate) fn hash_pii(field: PiiField, value: &str) -> String {
+    if is_pre_hashed(value) {
+        return value.to_string();
+    }
+    hash_value(&normalize_pii(field, value))
+}
+
+/// Hash a match-key property value, accepting either a single value or a JSON
+/// array of values (so a user can be matched on several emails, phone numbers, etc).
+pub(crate) fn hash_match_values(value: &str, hash_one: impl Fn(&str) -> String) -> Vec<String> {
+    if let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(value) {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(s) => Some(hash_one(s)),
+                // Scalars like `[12345, 67890]` are valid JSON too; coerce them to their
+                // plain string form (no JSON quoting) rather than silently dropping them.
+                serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                    Some(hash_one(&item.to_string()))
+                }
+                _ => None,
+            })
+            .collect()
+    } else {
+        vec![hash_one(value)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_name_from_str_parses_standard_events() {
+        assert_eq!(
+            PinterestEventName::from("checkout"),
+            PinterestEventName::Checkout
+        );
+        assert_eq!(
+            PinterestEventName::from("add_to_cart"),
+            PinterestEventName::AddToCart
+        );
+        assert_eq!(
+            PinterestEventName::from("page_visit"),
+            PinterestEventName::PageVisit
+        );
+        assert_eq!(
+            PinterestEventName::from("view_category"),
+            PinterestEventName::ViewCategory
+        );
+        assert_eq!(
+            PinterestEventName::from("search"),
+            PinterestEventName::Search
+        );
+        assert_eq!(
+            PinterestEventName::from("signup"),
+            PinterestEventName::Signup
+        );
+        assert_eq!(PinterestEventName::from("lead"), PinterestEventName::Lead);
+        assert_eq!(
+            PinterestEventName::from("watch_video"),
+            PinterestEventName::WatchVideo
+        );
+        assert_eq!(
+            PinterestEventName::from("add_to_wishlist"),
+            PinterestEventName::AddToWishlist
+        );
+    }
+
+    #[test]
+    fn event_name_from_str_falls_back_to_custom() {
+        assert_eq!(
+            PinterestEventName::from("something_custom"),
+            PinterestEventName::Custom("something_custom".to_string())
+        );
+    }
+
+    #[test]
+    fn event_name_as_wire_str_round_trips() {
+        assert_eq!(PinterestEventName::Checkout.as_wire_str(), "checkout");
+        assert_eq!(
+            PinterestEventName::Custom("something_custom".to_string()).as_wire_str(),
+            "something_custom"
+        );
+    }
+
+    #[test]
+    fn event_name_requires_value_and_currency_only_for_checkout_and_add_to_cart() {
+        assert!(PinterestEventName::Checkout.requires_value_and_currency());
+        assert!(PinterestEventName::AddToCart.requires_value_and_currency());
+        assert!(!PinterestEventName::PageVisit.requires_value_and_currency());
+        assert!(!PinterestEventName::Custom("something_custom".to_string())
+            .requires_value_and_currency());
+    }
+
+    #[test]
+    fn access_token_is_not_stale_when_no_expiry_is_tracked() {
+        let payload = PinterestPayload {
+            access_token_expires_at: None,
+            ..PinterestPayload::default()
+        };
+        assert!(!payload.access_token_is_stale());
+    }
+
+    #[test]
+    fn access_token_is_stale_once_past_expiry() {
+        let payload = PinterestPayload {
+            access_token_expires_at: Some(now_unix_secs() - 1),
+            ..PinterestPayload::default()
+        };
+        assert!(payload.access_token_is_stale());
+    }
+
+    #[test]
+    fn access_token_is_not_stale_before_expiry() {
+        let payload = PinterestPayload {
+            access_token_expires_at: Some(now_unix_secs() + 3600),
+            ..PinterestPayload::default()
+        };
+        assert!(!payload.access_token_is_stale());
+    }
+
+    #[test]
+    fn normalize_zip_strips_plus_four_suffix() {
+        assert_eq!(normalize_pii(PiiField::Zip, "12345-6789"), "12345");
+    }
+
+    #[test]
+    fn normalize_zip_keeps_short_non_numeric_code() {
+        assert_eq!(normalize_pii(PiiField::Zip, " SW1A 1AA "), "sw1a1aa");
+    }
+
+    #[test]
+    fn normalize_phone_strips_trunk_zero_after_country_code() {
+        assert_eq!(
+            normalize_pii(PiiField::Phone, "+33 0612345678"),
+            "33612345678"
+        );
+    }
+
+    #[test]
+    fn normalize_phone_does_not_mangle_area_code_zero() {
+        assert_eq!(
+            normalize_pii(PiiField::Phone, "+1 2065551234"),
+            "12065551234"
+        );
+    }
+
+    #[test]
+    fn normalize_phone_without_country_code_strips_leading_zero() {
+        assert_eq!(normalize_pii(PiiField::Phone, "0612345678"), "612345678");
+    }
+
+    #[test]
+    fn is_pre_hashed_detects_64_char_lowercase_hex() {
+        let hash = hash_value("test@example.com");
+        assert!(is_pre_hashed(&hash));
+        assert!(!is_pre_hashed("test@example.com"));
+    }
+
+    #[test]
+    fn hash_pii_passes_through_already_hashed_values() {
+        let hash = hash_value("test@example.com");
+        assert_eq!(hash_pii(PiiField::Email, &hash), hash);
+    }
+
+    #[test]
+    fn parse_value_parses_json_array() {
+        assert_eq!(parse_value("[\"a\",\"b\"]"), serde_json::json!(["a", "b"]));
+    }
+
+    #[test]
+    fn parse_value_parses_json_object() {
+        assert_eq!(
+            parse_value("{\"item_price\":9.99}"),
+            serde_json::json!({"item_price": 9.99})
+        );
+    }
+
+    #[test]
+    fn parse_value_coerces_bool_and_number() {
+        assert_eq!(parse_value("true"), serde_json::json!(true));
+        assert_eq!(parse_value("42"), serde_json::json!(42));
+    }
+
+    #[test]
+    fn parse_value_falls_back_to_string() {
+        assert_eq!(parse_value("hello"), serde_json::json!("hello"));
+    }
+
+    #[test]
+    fn parse_value_preserves_leading_zeros_as_string() {
+        // "00012345" isn't a valid JSON number literal (leading zeros aren't allowed),
+        // so it falls back to a string rather than silently losing the leading zeros.
+        assert_eq!(parse_value("00012345"), serde_json::json!("00012345"));
+    }
+
+    #[test]
+    fn parse_value_preserves_large_integer_precision() {
+        // Unlike an f64 round-trip, serde_json::Number keeps exact u64 integers intact.
+        assert_eq!(
+            parse_value("9007199254740993"),
+            serde_json::json!(9007199254740993u64)
+        );
+    }
+
+    #[test]
+    fn parse_value_falls_back_to_string_for_numbers_serde_json_cannot_represent() {
+        // "inf"/"Infinity"/"NaN" parse as f64 but aren't valid JSON numbers, and very
+        // large exponents overflow serde_json::Number — none of these should panic.
+        assert_eq!(parse_value("inf"), serde_json::json!("inf"));
+        assert_eq!(parse_value("Infinity"), serde_json::json!("Infinity"));
+        assert_eq!(parse_value("NaN"), serde_json::json!("NaN"));
+        assert_eq!(parse_value("1e400"), serde_json::json!("1e400"));
+    }
+
+    #[test]
+    fn hash_match_values_hashes_json_array_of_emails() {
+        let hashed = hash_match_values("[\"a@example.com\",\"b@example.com\"]", |v| {
+            hash_pii(PiiField::Email, v)
+        });
+        assert_eq!(
+            hashed,
+            vec![
+                hash_pii(PiiField::Email, "a@example.com"),
+                hash_pii(PiiField::Email, "b@example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn hash_match_values_hashes_single_value() {
+        let hashed = hash_match_values("a@example.com", |v| hash_pii(PiiField::Email, v));
+        assert_eq!(hashed, vec![hash_pii(PiiField::Email, "a@example.com")]);
+    }
+
+    #[test]
+    fn hash_match_values_hashes_numeric_array_items() {
+        let hashed = hash_match_values("[12345, 67890]", |v| hash_pii(PiiField::Zip, v));
+        assert_eq!(
+            hashed,
+            vec![
+                hash_pii(PiiField::Zip, "12345"),
+                hash_pii(PiiField::Zip, "67890"),
+            ]
+        );
+    }
+}